@@ -0,0 +1,29 @@
+use crate::types::{Distance, ScoreType, VectorElementType};
+
+/// A scoring function between two vectors, selected at runtime by a
+/// collection's configured [`Distance`].
+pub trait Metric {
+    fn distance(&self) -> Distance;
+
+    fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType;
+
+    fn preprocess(&self, vector: &[VectorElementType]) -> Option<Vec<VectorElementType>>;
+
+    /// Scores `query` against every one of `candidates`, writing into `out`.
+    ///
+    /// The default just re-enters [`Metric::similarity`] per candidate.
+    /// Implementors with a register-blocked batch kernel (e.g.
+    /// `DotProductMetric`) override this to amortize the query load across
+    /// a group of candidates instead.
+    fn similarity_batch(
+        &self,
+        query: &[VectorElementType],
+        candidates: &[&[VectorElementType]],
+        out: &mut [ScoreType],
+    ) {
+        debug_assert_eq!(candidates.len(), out.len());
+        for (candidate, score) in candidates.iter().zip(out.iter_mut()) {
+            *score = self.similarity(query, candidate);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! A `std::simd`-based metric backend.
+//!
+//! The hand-written kernels in [`super::simple`] need a dedicated
+//! implementation per instruction set (SSE/AVX2/AVX-512f/NEON), and targets
+//! without a hand-written kernel (PowerPC/AltiVec, WASM SIMD, RISC-V...)
+//! silently fall back to the scalar loop. [`Simd<f32, LANES>`] compiles to
+//! the right vector instructions for whatever target it's built for, so a
+//! single chunked loop here covers all of them.
+//!
+//! Requires `#![feature(portable_simd)]` at the crate root and the
+//! `portable_simd` feature on this crate; see [`Backend`] for how a
+//! downstream build picks between this, the hand-written intrinsics, and
+//! plain scalar.
+#![cfg(feature = "portable_simd")]
+
+use std::simd::{LaneCount, Simd, SimdFloat, SupportedLaneCount};
+
+use crate::types::{Distance, ScoreType, VectorElementType};
+
+use super::metric::Metric;
+
+/// Number of `f32` lanes per chunk. 8 matches AVX2's `__m256` width, which
+/// keeps this backend's numerics close to the hand-written AVX2 kernels.
+const LANES: usize = 8;
+
+/// Selects which metric backend a downstream build compiles against,
+/// analogous to other crates' "explicit SIMD" build switches. This lets a
+/// target without hand-written intrinsics (PowerPC/AltiVec, WASM) opt into
+/// `Portable`, while x86/aarch64 builds can force `Intrinsics` to keep using
+/// [`super::simple`]'s hand-written kernels, or `Scalar` to disable
+/// vectorization entirely (e.g. for debugging numerical differences).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Hand-written per-architecture intrinsics in [`super::simple`].
+    Intrinsics,
+    /// This module's `std::simd`-based generic backend.
+    Portable,
+    /// Plain scalar loops, no vectorization.
+    Scalar,
+}
+
+/// The backend this build was compiled to use, selected by Cargo feature:
+/// `simd-intrinsics` or `simd-scalar` override the default of `Portable`.
+/// Exactly one of the two override features should be enabled at a time.
+#[cfg(feature = "simd-intrinsics")]
+const SELECTED_BACKEND: Backend = Backend::Intrinsics;
+#[cfg(all(feature = "simd-scalar", not(feature = "simd-intrinsics")))]
+const SELECTED_BACKEND: Backend = Backend::Scalar;
+#[cfg(not(any(feature = "simd-intrinsics", feature = "simd-scalar")))]
+const SELECTED_BACKEND: Backend = Backend::Portable;
+
+pub struct PortableEuclidMetric {}
+
+pub struct PortableDotProductMetric {}
+
+pub struct PortableCosineMetric {}
+
+impl Metric for PortableEuclidMetric {
+    fn distance(&self) -> Distance {
+        Distance::Euclid
+    }
+
+    fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        match SELECTED_BACKEND {
+            Backend::Portable => euclid_similarity_simd::<LANES>(v1, v2),
+            Backend::Scalar => euclid_similarity_simd::<1>(v1, v2),
+            Backend::Intrinsics => super::simple::EuclidMetric {}.similarity(v1, v2),
+        }
+    }
+
+    fn preprocess(&self, _vector: &[VectorElementType]) -> Option<Vec<VectorElementType>> {
+        None
+    }
+}
+
+impl Metric for PortableDotProductMetric {
+    fn distance(&self) -> Distance {
+        Distance::Dot
+    }
+
+    fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        match SELECTED_BACKEND {
+            Backend::Portable => dot_similarity_simd::<LANES>(v1, v2),
+            Backend::Scalar => dot_similarity_simd::<1>(v1, v2),
+            Backend::Intrinsics => super::simple::DotProductMetric {}.similarity(v1, v2),
+        }
+    }
+
+    fn preprocess(&self, _vector: &[VectorElementType]) -> Option<Vec<VectorElementType>> {
+        None
+    }
+}
+
+impl Metric for PortableCosineMetric {
+    fn distance(&self) -> Distance {
+        Distance::Cosine
+    }
+
+    fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        match SELECTED_BACKEND {
+            Backend::Portable => dot_similarity_simd::<LANES>(v1, v2),
+            Backend::Scalar => dot_similarity_simd::<1>(v1, v2),
+            Backend::Intrinsics => super::simple::CosineMetric {}.similarity(v1, v2),
+        }
+    }
+
+    fn preprocess(&self, vector: &[VectorElementType]) -> Option<Vec<VectorElementType>> {
+        match SELECTED_BACKEND {
+            Backend::Portable => Some(cosine_preprocess_simd::<LANES>(vector)),
+            Backend::Scalar => Some(cosine_preprocess_simd::<1>(vector)),
+            Backend::Intrinsics => super::simple::CosineMetric {}.preprocess(vector),
+        }
+    }
+}
+
+fn euclid_similarity_simd<const N: usize>(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let n = v1.len();
+    let m = n - (n % N);
+    let mut acc = Simd::<f32, N>::splat(0.0);
+    for i in (0..m).step_by(N) {
+        let a = Simd::<f32, N>::from_slice(&v1[i..i + N]);
+        let b = Simd::<f32, N>::from_slice(&v2[i..i + N]);
+        let d = a - b;
+        acc += d * d;
+    }
+    let mut res = acc.reduce_sum();
+    for i in m..n {
+        res += (v1[i] - v2[i]).powi(2);
+    }
+    -res.sqrt()
+}
+
+fn dot_similarity_simd<const N: usize>(
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let n = v1.len();
+    let m = n - (n % N);
+    let mut acc = Simd::<f32, N>::splat(0.0);
+    for i in (0..m).step_by(N) {
+        let a = Simd::<f32, N>::from_slice(&v1[i..i + N]);
+        let b = Simd::<f32, N>::from_slice(&v2[i..i + N]);
+        acc += a * b;
+    }
+    let mut res = acc.reduce_sum();
+    for i in m..n {
+        res += v1[i] * v2[i];
+    }
+    res
+}
+
+fn cosine_preprocess_simd<const N: usize>(vector: &[VectorElementType]) -> Vec<VectorElementType>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let n = vector.len();
+    let m = n - (n % N);
+    let mut acc = Simd::<f32, N>::splat(0.0);
+    for i in (0..m).step_by(N) {
+        let a = Simd::<f32, N>::from_slice(&vector[i..i + N]);
+        acc += a * a;
+    }
+    let mut length = acc.reduce_sum();
+    for v in vector.iter().take(n).skip(m) {
+        length += v.powi(2);
+    }
+    length = length.sqrt();
+    vector.iter().map(|x| x / length).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::simple::{
+        CosineMetric as SimpleCosineMetric, DotProductMetric as SimpleDotProductMetric,
+        EuclidMetric as SimpleEuclidMetric,
+    };
+
+    /// Asserts the portable backend agrees bit-for-bit with whichever
+    /// hand-written kernel [`super::simple`]'s runtime dispatch picks on
+    /// this host (SSE/AVX2/AVX-512f/NEON, or its own scalar fallback).
+    #[test]
+    fn test_portable_matches_simple_backend() {
+        let v1: Vec<f32> = vec![
+            10., 11., 12., 13., 14., 15., 16., 17., 18., 19., 20., 21., 22., 23., 24., 25., 26.,
+            27., 28., 29., 30., 31.,
+        ];
+        let v2: Vec<f32> = vec![
+            40., 41., 42., 43., 44., 45., 46., 47., 48., 49., 50., 51., 52., 53., 54., 55., 56.,
+            57., 58., 59., 60., 61.,
+        ];
+
+        assert_eq!(
+            euclid_similarity_simd::<LANES>(&v1, &v2),
+            SimpleEuclidMetric {}.similarity(&v1, &v2)
+        );
+        assert_eq!(
+            dot_similarity_simd::<LANES>(&v1, &v2),
+            SimpleDotProductMetric {}.similarity(&v1, &v2)
+        );
+        assert_eq!(
+            cosine_preprocess_simd::<LANES>(&v1),
+            SimpleCosineMetric {}.preprocess(&v1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_scalar_lanes_match_portable_lanes() {
+        let v1: Vec<f32> = (0..37).map(|i| i as f32).collect();
+        let v2: Vec<f32> = (0..37).map(|i| (i as f32) * 1.5 - 3.0).collect();
+
+        assert_eq!(
+            euclid_similarity_simd::<LANES>(&v1, &v2),
+            euclid_similarity_simd::<1>(&v1, &v2)
+        );
+        assert_eq!(
+            dot_similarity_simd::<LANES>(&v1, &v2),
+            dot_similarity_simd::<1>(&v1, &v2)
+        );
+        assert_eq!(
+            cosine_preprocess_simd::<LANES>(&v1),
+            cosine_preprocess_simd::<1>(&v1)
+        );
+    }
+}
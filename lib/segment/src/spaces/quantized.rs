@@ -0,0 +1,218 @@
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
+
+use crate::types::{Distance, ScoreType, VectorElementType};
+
+/// A vector quantized to `i8` with a single per-vector scale, as used by
+/// [`QuantizedDotProductMetric`] and [`QuantizedEuclidMetric`].
+///
+/// Quantization is symmetric: `scale = max(|x_i|) / 127`, `q_i = round(x_i / scale)`.
+/// `sq_norm` is precomputed over the quantized values so the hot similarity
+/// path never has to re-walk the vector to recover it.
+pub struct QuantizedVector {
+    pub values: Vec<i8>,
+    pub scale: f32,
+    /// `Σ q_i²`, used by the Euclid expansion to avoid recomputing the norm per pair.
+    pub sq_norm: i32,
+}
+
+impl QuantizedVector {
+    pub fn quantize(vector: &[VectorElementType]) -> QuantizedVector {
+        let amax = vector.iter().fold(0f32, |acc, x| acc.max(x.abs()));
+        let scale = if amax == 0.0 { 1.0 } else { amax / 127.0 };
+        let values: Vec<i8> = vector
+            .iter()
+            .map(|x| (x / scale).round().clamp(-127.0, 127.0) as i8)
+            .collect();
+        let sq_norm = values.iter().map(|&v| (v as i32) * (v as i32)).sum();
+        QuantizedVector {
+            values,
+            scale,
+            sq_norm,
+        }
+    }
+}
+
+/// Mirrors [`super::metric::Metric`] for the quantized storage mode: the
+/// pair of quantized vectors already carries the scale and norm bookkeeping
+/// needed to dequantize the integer accumulator back into a [`ScoreType`].
+pub trait QuantizedMetric {
+    fn distance(&self) -> Distance;
+
+    fn similarity(&self, v1: &QuantizedVector, v2: &QuantizedVector) -> ScoreType;
+}
+
+pub struct QuantizedDotProductMetric {}
+
+pub struct QuantizedEuclidMetric {}
+
+impl QuantizedMetric for QuantizedDotProductMetric {
+    fn distance(&self) -> Distance {
+        Distance::Dot
+    }
+
+    fn similarity(&self, v1: &QuantizedVector, v2: &QuantizedVector) -> ScoreType {
+        let dot = quantized_dot(v1, v2);
+        v1.scale * v2.scale * dot as ScoreType
+    }
+}
+
+impl QuantizedMetric for QuantizedEuclidMetric {
+    fn distance(&self) -> Distance {
+        Distance::Euclid
+    }
+
+    fn similarity(&self, v1: &QuantizedVector, v2: &QuantizedVector) -> ScoreType {
+        // ||a - b||² = Σa² + Σb² - 2·Σab, each term dequantized by its own scale.
+        let dot = quantized_dot(v1, v2);
+        let sq = v1.scale * v1.scale * v1.sq_norm as ScoreType
+            + v2.scale * v2.scale * v2.sq_norm as ScoreType
+            - 2.0 * v1.scale * v2.scale * dot as ScoreType;
+        -sq.max(0.0).sqrt()
+    }
+}
+
+/// Widening `i8 × i8` dot product, dispatched to the best available kernel.
+fn quantized_dot(v1: &QuantizedVector, v2: &QuantizedVector) -> i32 {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return unsafe { quantized_dot_avx2(&v1.values, &v2.values) };
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon")
+            && std::arch::is_aarch64_feature_detected!("dotprod")
+        {
+            return unsafe { quantized_dot_neon(&v1.values, &v2.values) };
+        }
+    }
+    quantized_dot_scalar(&v1.values, &v2.values)
+}
+
+fn quantized_dot_scalar(v1: &[i8], v2: &[i8]) -> i32 {
+    v1.iter()
+        .zip(v2.iter())
+        .map(|(&a, &b)| a as i32 * b as i32)
+        .sum()
+}
+
+/// Sign-extends each `i8` lane to `i16` with `_mm256_cvtepi8_epi16`, then
+/// uses `_mm256_madd_epi16` to multiply lane pairs and widen-add them into
+/// `i32` directly.
+///
+/// An earlier version of this kernel used `_mm256_maddubs_epi16` (unsigned
+/// × signed byte multiply) with a zero-point shift to handle the signed
+/// left operand. That intrinsic saturates its `i16` output *inside* the
+/// multiply-add itself, before any widening happens: with the left operand
+/// shifted into `0..=255`, two adjacent byte products (up to `255 * 127`
+/// each) already exceed `i16::MAX`, so any pair of vectors with a flat magnitude profile
+/// (unremarkable after `scale = amax / 127` quantization) silently produced
+/// a wrong, saturated dot product. Sign-extending to `i16` first avoids the
+/// saturating intrinsic entirely: `_mm256_madd_epi16`'s per-lane product and
+/// pairwise sum both fit comfortably in `i32` for any `i8 × i8` input.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn quantized_dot_avx2(v1: &[i8], v2: &[i8]) -> i32 {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut acc = _mm256_setzero_si256();
+    for i in (0..m).step_by(16) {
+        let a = _mm_loadu_si128(v1[i..].as_ptr() as *const __m128i);
+        let b = _mm_loadu_si128(v2[i..].as_ptr() as *const __m128i);
+        let a16 = _mm256_cvtepi8_epi16(a);
+        let b16 = _mm256_cvtepi8_epi16(b);
+        let prod32 = _mm256_madd_epi16(a16, b16);
+        acc = _mm256_add_epi32(acc, prod32);
+    }
+    let mut res = hsum256_i32_avx2(acc);
+    for i in m..n {
+        res += v1[i] as i32 * v2[i] as i32;
+    }
+    res
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn hsum256_i32_avx2(x: __m256i) -> i32 {
+    let hi = _mm256_extracti128_si256(x, 1);
+    let lo = _mm256_castsi256_si128(x);
+    let sum128 = _mm_add_epi32(hi, lo);
+    let hi64 = _mm_unpackhi_epi64(sum128, sum128);
+    let sum64 = _mm_add_epi32(sum128, hi64);
+    let hi32 = _mm_shuffle_epi32(sum64, 0b01);
+    let sum32 = _mm_add_epi32(sum64, hi32);
+    _mm_cvtsi128_si32(sum32)
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon,dotprod")]
+unsafe fn quantized_dot_neon(v1: &[i8], v2: &[i8]) -> i32 {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let mut acc = vdupq_n_s32(0);
+    for i in (0..m).step_by(16) {
+        let a = vld1q_s8(v1[i..].as_ptr());
+        let b = vld1q_s8(v2[i..].as_ptr());
+        acc = vdotq_s32(acc, a, b);
+    }
+    let mut res = vaddvq_s32(acc);
+    for i in m..n {
+        res += v1[i] as i32 * v2[i] as i32;
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn test_quantized_dot_avx2() {
+        if !is_x86_feature_detected!("avx2") {
+            println!("AVX2 test skiped");
+            return;
+        }
+
+        // Regression test: a flat magnitude profile (every lane at the
+        // quantization ceiling) used to silently saturate inside
+        // `_mm256_maddubs_epi16`, computing 4080 instead of 516128.
+        let v1: Vec<i8> = vec![127; 32];
+        let v2: Vec<i8> = vec![127; 32];
+        let dot = unsafe { quantized_dot_avx2(&v1, &v2) };
+        assert_eq!(dot, 516_128);
+        assert_eq!(dot, quantized_dot_scalar(&v1, &v2));
+
+        let v1: Vec<i8> = (0..37).map(|i| (i % 17) as i8 - 8).collect();
+        let v2: Vec<i8> = (0..37).map(|i| (i % 11) as i8 - 5).collect();
+        assert_eq!(
+            unsafe { quantized_dot_avx2(&v1, &v2) },
+            quantized_dot_scalar(&v1, &v2)
+        );
+    }
+
+    #[test]
+    fn test_quantize_round_trip() {
+        let vector: Vec<VectorElementType> = vec![1.0, -2.0, 3.0, -4.0, 127.0];
+        let quantized = QuantizedVector::quantize(&vector);
+        assert_eq!(quantized.values.last().copied(), Some(127));
+        let dequantized: Vec<VectorElementType> = quantized
+            .values
+            .iter()
+            .map(|&q| q as VectorElementType * quantized.scale)
+            .collect();
+        assert_eq!(dequantized, vector);
+        assert_eq!(
+            quantized.sq_norm,
+            quantized.values.iter().map(|&v| v as i32 * v as i32).sum()
+        );
+    }
+}
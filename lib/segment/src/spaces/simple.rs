@@ -20,12 +20,20 @@ pub struct CosineMetric {}
 
 pub struct EuclidMetric {}
 
+pub struct ManhattanMetric {}
+
 impl Metric for EuclidMetric {
     fn distance(&self) -> Distance {
         Distance::Euclid
     }
 
     fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return unsafe { euclid_similarity_avx512f(v1, v2) };
+            }
+        }
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         {
             if is_x86_feature_detected!("avx2") {
@@ -35,6 +43,12 @@ impl Metric for EuclidMetric {
                 return unsafe { euclid_similarity_sse(v1, v2) };
             }
         }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return unsafe { euclid_similarity_neon(v1, v2) };
+            }
+        }
         euclid_similarity(v1, v2)
     }
 
@@ -49,6 +63,12 @@ impl Metric for DotProductMetric {
     }
 
     fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return unsafe { dot_similarity_avx512f(v1, v2) };
+            }
+        }
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         {
             if is_x86_feature_detected!("avx2") {
@@ -58,12 +78,47 @@ impl Metric for DotProductMetric {
                 return unsafe { dot_similarity_sse(v1, v2) };
             }
         }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return unsafe { dot_similarity_neon(v1, v2) };
+            }
+        }
         dot_similarity(v1, v2)
     }
 
     fn preprocess(&self, _vector: &[VectorElementType]) -> Option<Vec<VectorElementType>> {
         None
     }
+
+    /// Candidates are processed in register-blocked groups so the query's
+    /// lanes are loaded once per dimension block and reused across the
+    /// whole group, instead of re-entering [`Metric::similarity`] (and thus
+    /// re-dispatching and re-loading the query) per candidate.
+    fn similarity_batch(
+        &self,
+        query: &[VectorElementType],
+        candidates: &[&[VectorElementType]],
+        out: &mut [ScoreType],
+    ) {
+        debug_assert_eq!(candidates.len(), out.len());
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return unsafe { dot_similarity_batch_avx2(query, candidates, out) };
+            }
+            if is_x86_feature_detected!("sse") {
+                return unsafe { dot_similarity_batch_sse(query, candidates, out) };
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return unsafe { dot_similarity_batch_neon(query, candidates, out) };
+            }
+        }
+        dot_similarity_batch_scalar(query, candidates, out)
+    }
 }
 
 impl Metric for CosineMetric {
@@ -72,6 +127,12 @@ impl Metric for CosineMetric {
     }
 
     fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return unsafe { dot_similarity_avx512f(v1, v2) };
+            }
+        }
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         {
             if is_x86_feature_detected!("avx2") {
@@ -81,10 +142,22 @@ impl Metric for CosineMetric {
                 return unsafe { dot_similarity_sse(v1, v2) };
             }
         }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return unsafe { dot_similarity_neon(v1, v2) };
+            }
+        }
         dot_similarity(v1, v2)
     }
 
     fn preprocess(&self, vector: &[VectorElementType]) -> Option<Vec<VectorElementType>> {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return Some(unsafe { cosine_preprocess_avx512f(vector) });
+            }
+        }
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         {
             if is_x86_feature_detected!("avx2") {
@@ -94,8 +167,71 @@ impl Metric for CosineMetric {
                 return Some(unsafe { cosine_preprocess_sse(vector) });
             }
         }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return Some(unsafe { cosine_preprocess_neon(vector) });
+            }
+        }
         Some(cosine_preprocess(vector))
     }
+
+    /// Cosine vectors are already length-normalized by [`Metric::preprocess`],
+    /// so scoring against them is a plain dot product and this reuses
+    /// [`DotProductMetric`]'s batch kernels.
+    fn similarity_batch(
+        &self,
+        query: &[VectorElementType],
+        candidates: &[&[VectorElementType]],
+        out: &mut [ScoreType],
+    ) {
+        DotProductMetric {}.similarity_batch(query, candidates, out)
+    }
+}
+
+impl Metric for ManhattanMetric {
+    fn distance(&self) -> Distance {
+        Distance::Manhattan
+    }
+
+    fn similarity(&self, v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return unsafe { manhattan_similarity_avx512f(v1, v2) };
+            }
+        }
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return unsafe { manhattan_similarity_avx2(v1, v2) };
+            }
+            if is_x86_feature_detected!("sse") {
+                return unsafe { manhattan_similarity_sse(v1, v2) };
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return unsafe { manhattan_similarity_neon(v1, v2) };
+            }
+        }
+        manhattan_similarity(v1, v2)
+    }
+
+    fn preprocess(&self, _vector: &[VectorElementType]) -> Option<Vec<VectorElementType>> {
+        None
+    }
+}
+
+fn dot_similarity_batch_scalar(
+    query: &[VectorElementType],
+    candidates: &[&[VectorElementType]],
+    out: &mut [ScoreType],
+) {
+    for (candidate, score) in candidates.iter().zip(out.iter_mut()) {
+        *score = dot_similarity(query, candidate);
+    }
 }
 
 fn euclid_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
@@ -118,11 +254,20 @@ fn dot_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreTy
     v1.iter().zip(v2).map(|(a, b)| a * b).sum()
 }
 
-#[cfg(all(
-    target_arch = "x86_64",
-    target_feature = "avx512f"))]
+fn manhattan_similarity(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    let s: ScoreType = v1
+        .iter()
+        .copied()
+        .zip(v2.iter().copied())
+        .map(|(a, b)| (a - b).abs())
+        .sum();
+    -s
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
 unsafe fn euclid_similarity_avx512f(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
-    let n2 = v1.len();
+    let n = v1.len();
     let m = n - (n % 16);
     let mut sum512: __m512 = _mm512_setzero_ps();
     for i in (0..m).step_by(16) {
@@ -136,9 +281,8 @@ unsafe fn euclid_similarity_avx512f(v1: &[VectorElementType], v2: &[VectorElemen
     -res.sqrt()
 }
 
-#[cfg(all(
-    target_arch = "x86_64",
-    target_feature = "avx512f"))]
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
 unsafe fn cosine_preprocess_avx512f(vector: &[VectorElementType]) -> Vec<VectorElementType> {
     let n = vector.len();
     let m = n - (n % 16);
@@ -158,9 +302,8 @@ unsafe fn cosine_preprocess_avx512f(vector: &[VectorElementType]) -> Vec<VectorE
     vector.iter().map(|x| x / length).collect()
 }
 
-#[cfg(all(
-    target_arch = "x86_64",
-    target_feature = "avx512f"))]
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
 unsafe fn dot_similarity_avx512f(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
     let n = v1.len();
     let m = n - (n % 16);
@@ -175,6 +318,25 @@ unsafe fn dot_similarity_avx512f(v1: &[VectorElementType], v2: &[VectorElementTy
     res
 }
 
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+unsafe fn manhattan_similarity_avx512f(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 16);
+    let sign_mask512 = _mm512_set1_ps(-0.0);
+    let mut sum512: __m512 = _mm512_setzero_ps();
+    for i in (0..m).step_by(16) {
+        let sub512: __m512 = _mm512_sub_ps(_mm512_loadu_ps(&v1[i]), _mm512_loadu_ps(&v2[i]));
+        let abs512 = _mm512_andnot_ps(sign_mask512, sub512);
+        sum512 = _mm512_add_ps(abs512, sum512);
+    }
+    let mut res = _mm512_mask_reduce_add_ps(u16::MAX, sum512);
+    for i in m..n {
+        res += (v1[i] - v2[i]).abs();
+    }
+    -res
+}
+
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 #[target_feature(enable = "avx2")]
 unsafe fn hsum256_ps_avx2(x: __m256) -> f32 {
@@ -242,6 +404,56 @@ unsafe fn dot_similarity_avx2(v1: &[VectorElementType], v2: &[VectorElementType]
     res
 }
 
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn manhattan_similarity_avx2(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 8);
+    let sign_mask256 = _mm256_set1_ps(-0.0);
+    let mut sum256: __m256 = _mm256_setzero_ps();
+    for i in (0..m).step_by(8) {
+        let sub256: __m256 = _mm256_sub_ps(_mm256_loadu_ps(&v1[i]), _mm256_loadu_ps(&v2[i]));
+        let abs256 = _mm256_andnot_ps(sign_mask256, sub256);
+        sum256 = _mm256_add_ps(abs256, sum256);
+    }
+    let mut res = hsum256_ps_avx2(sum256);
+    for i in m..n {
+        res += (v1[i] - v2[i]).abs();
+    }
+    -res
+}
+
+/// Register-blocked dot product: 4 candidates × 8 lanes. The query is
+/// loaded once per dimension block and broadcast across a dedicated
+/// `__m256` accumulator per candidate, so a group of 4 shares every query
+/// load instead of re-fetching it per candidate.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn dot_similarity_batch_avx2(
+    query: &[VectorElementType],
+    candidates: &[&[VectorElementType]],
+    out: &mut [ScoreType],
+) {
+    let n = query.len();
+    let m = n - (n % 8);
+    for (group, out_group) in candidates.chunks(4).zip(out.chunks_mut(4)) {
+        let mut acc = [_mm256_setzero_ps(); 4];
+        for i in (0..m).step_by(8) {
+            let q = _mm256_loadu_ps(&query[i]);
+            for (j, candidate) in group.iter().enumerate() {
+                acc[j] = _mm256_fmadd_ps(q, _mm256_loadu_ps(&candidate[i]), acc[j]);
+            }
+        }
+        for (j, candidate) in group.iter().enumerate() {
+            let mut res = hsum256_ps_avx2(acc[j]);
+            for i in m..n {
+                res += query[i] * candidate[i];
+            }
+            out_group[j] = res;
+        }
+    }
+}
+
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
 #[target_feature(enable = "sse")]
 unsafe fn hsum128_ps_sse(x: __m128) -> f32 {
@@ -306,6 +518,54 @@ unsafe fn dot_similarity_sse(v1: &[VectorElementType], v2: &[VectorElementType])
     res
 }
 
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse")]
+unsafe fn manhattan_similarity_sse(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 4);
+    let sign_mask128 = _mm_set1_ps(-0.0);
+    let mut sum128: __m128 = _mm_setzero_ps();
+    for i in (0..m).step_by(4) {
+        let sub128: __m128 = _mm_sub_ps(_mm_loadu_ps(&v1[i]), _mm_loadu_ps(&v2[i]));
+        let abs128 = _mm_andnot_ps(sign_mask128, sub128);
+        sum128 = _mm_add_ps(abs128, sum128);
+    }
+    let mut res = hsum128_ps_sse(sum128);
+    for i in m..n {
+        res += (v1[i] - v2[i]).abs();
+    }
+    -res
+}
+
+/// SSE counterpart of [`dot_similarity_batch_avx2`]: 4 candidates × 4 lanes.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse")]
+unsafe fn dot_similarity_batch_sse(
+    query: &[VectorElementType],
+    candidates: &[&[VectorElementType]],
+    out: &mut [ScoreType],
+) {
+    let n = query.len();
+    let m = n - (n % 4);
+    for (group, out_group) in candidates.chunks(4).zip(out.chunks_mut(4)) {
+        let mut acc = [_mm_setzero_ps(); 4];
+        for i in (0..m).step_by(4) {
+            let q = _mm_loadu_ps(&query[i]);
+            for (j, candidate) in group.iter().enumerate() {
+                let c = _mm_mul_ps(q, _mm_loadu_ps(&candidate[i]));
+                acc[j] = _mm_add_ps(c, acc[j]);
+            }
+        }
+        for (j, candidate) in group.iter().enumerate() {
+            let mut res = hsum128_ps_sse(acc[j]);
+            for i in m..n {
+                res += query[i] * candidate[i];
+            }
+            out_group[j] = res;
+        }
+    }
+}
+
 #[cfg(all(
     target_arch = "aarch64",
     target_feature = "neon"))]
@@ -364,6 +624,54 @@ unsafe fn dot_similarity_neon(v1: &[VectorElementType], v2: &[VectorElementType]
     res as ScoreType
 }
 
+#[cfg(all(
+    target_arch = "aarch64",
+    target_feature = "neon"))]
+unsafe fn manhattan_similarity_neon(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    let n = v1.len();
+    let m = n - (n % 4);
+    let mut res : f64 = 0.0;
+    for i in (0..m).step_by(4) {
+        let a = vld1q_f32(&v1[i]);
+        let b = vld1q_f32(&v2[i]);
+        let c = vabsq_f32(vsubq_f32(a, b));
+        res += vaddvq_f32(c) as f64;
+    }
+    for i in m..n {
+        res += (v1[i] - v2[i]).abs() as f64;
+    }
+    -res as ScoreType
+}
+
+/// NEON counterpart of [`dot_similarity_batch_avx2`]: 4 candidates × 4 lanes.
+#[cfg(all(
+    target_arch = "aarch64",
+    target_feature = "neon"))]
+unsafe fn dot_similarity_batch_neon(
+    query: &[VectorElementType],
+    candidates: &[&[VectorElementType]],
+    out: &mut [ScoreType],
+) {
+    let n = query.len();
+    let m = n - (n % 4);
+    for (group, out_group) in candidates.chunks(4).zip(out.chunks_mut(4)) {
+        let mut acc: [float32x4_t; 4] = [vdupq_n_f32(0.0); 4];
+        for i in (0..m).step_by(4) {
+            let q = vld1q_f32(&query[i]);
+            for (j, candidate) in group.iter().enumerate() {
+                acc[j] = vmlaq_f32(acc[j], q, vld1q_f32(&candidate[i]));
+            }
+        }
+        for (j, candidate) in group.iter().enumerate() {
+            let mut res = vaddvq_f32(acc[j]);
+            for i in m..n {
+                res += query[i] * candidate[i];
+            }
+            out_group[j] = res;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -378,9 +686,7 @@ mod tests {
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     #[test]
     fn test_simd() {
-        #[cfg(all(
-            target_arch = "x86_64",
-            target_feature = "avx512f"))]
+        #[cfg(target_arch = "x86_64")]
         {
             if is_x86_feature_detected!("avx512f") {
                 println!("avx512f test passed");
@@ -461,6 +767,40 @@ mod tests {
         }
     }
 
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn test_batch_simd() {
+        let query: Vec<f32> = (0..22).map(|i| i as f32).collect();
+        let candidates_owned: Vec<Vec<f32>> = (0..6)
+            .map(|c| (0..22).map(|i| (i + c) as f32 * 1.5).collect())
+            .collect();
+        let candidates: Vec<&[f32]> = candidates_owned.iter().map(|v| v.as_slice()).collect();
+        let expected: Vec<f32> = candidates
+            .iter()
+            .map(|c| dot_similarity(&query, c))
+            .collect();
+
+        let mut out = vec![0.0; candidates.len()];
+        dot_similarity_batch_scalar(&query, &candidates, &mut out);
+        assert_eq!(out, expected);
+
+        if is_x86_feature_detected!("sse") {
+            let mut out = vec![0.0; candidates.len()];
+            unsafe { dot_similarity_batch_sse(&query, &candidates, &mut out) };
+            assert_eq!(out, expected);
+        } else {
+            println!("SSE batch test skiped");
+        }
+
+        if is_x86_feature_detected!("avx2") {
+            let mut out = vec![0.0; candidates.len()];
+            unsafe { dot_similarity_batch_avx2(&query, &candidates, &mut out) };
+            assert_eq!(out, expected);
+        } else {
+            println!("AVX2 batch test skiped");
+        }
+    }
+
     #[cfg(target_arch = "aarch64")]
     #[test]
     fn test_neon() {
@@ -485,6 +825,18 @@ mod tests {
             let cosine_simd = unsafe { cosine_preprocess_neon(&v1) };
             let cosine = cosine_preprocess(&v1);
             assert_eq!(cosine_simd, cosine);
+
+            let candidates_owned: Vec<Vec<f32>> = (0..6)
+                .map(|c| (0..22).map(|i| (i + c) as f32 * 1.5).collect())
+                .collect();
+            let candidates: Vec<&[f32]> = candidates_owned.iter().map(|v| v.as_slice()).collect();
+            let expected: Vec<f32> = candidates
+                .iter()
+                .map(|c| dot_similarity(&v1, c))
+                .collect();
+            let mut out = vec![0.0; candidates.len()];
+            unsafe { dot_similarity_batch_neon(&v1, &candidates, &mut out) };
+            assert_eq!(out, expected);
         } else {
             println!("neon test skiped");
         }
@@ -0,0 +1,10 @@
+pub type VectorElementType = f32;
+pub type ScoreType = f32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Distance {
+    Cosine,
+    Euclid,
+    Dot,
+    Manhattan,
+}